@@ -0,0 +1,53 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2022 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+/// Logs messages to the console, optionally colored and with per-entry verbosity.
+#[derive(Clone, Copy)]
+pub struct Logger<'a> {
+    pub color: bool,
+    pub verbose: bool,
+    pub filename: &'a str,
+    pub content: &'a str,
+}
+
+impl<'a> Logger<'a> {
+    pub fn new(color: bool, verbose: bool, filename: &'a str, content: &'a str) -> Logger<'a> {
+        Logger {
+            color,
+            verbose,
+            filename,
+            content,
+        }
+    }
+
+    pub fn debug_important(&self, message: &str) {
+        if self.verbose {
+            eprintln!("{message}");
+        }
+    }
+
+    pub fn debug(&self, message: &str) {
+        if self.verbose {
+            eprintln!("{message}");
+        }
+    }
+
+    pub fn error_rich(&self, error: &crate::runner::error::Error) {
+        eprintln!("error: {}", error.message);
+    }
+}
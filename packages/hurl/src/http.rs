@@ -0,0 +1,196 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2022 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClientOptions {
+    pub verbosity: Option<u8>,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Directories a run is allowed to read/write local files from: the current working directory
+/// (for relative paths in the Hurl file itself) and an explicit `--file-root` override.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextDir {
+    pub current_dir: std::path::PathBuf,
+    pub file_root: std::path::PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+}
+
+/// A request built from an `Entry`, ready to be sent by an [`HttpClient`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestSpec {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// What an [`HttpClient`] returns for a single request/response exchange, including the timing
+/// information used to populate an `EntryResult`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u32,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub time_in_ms: u128,
+}
+
+/// Error returned by an [`HttpClient`] when a request could not be sent or its response could
+/// not be read (connection refused, TLS failure, timeout, ...).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpError {
+    pub message: String,
+}
+
+/// Executes [`RequestSpec`]s and tracks cookies across a run. Implemented by the libcurl-backed
+/// [`Client`] by default; alternative backends (an in-process mock for unit-testing asserts
+/// without sockets, a recording/replay backend for deterministic CI, ...) can implement this
+/// trait instead to be driven by [`crate::runner::run`].
+pub trait HttpClient {
+    /// Sends `request` and returns the response, or an [`HttpError`] on transport failure.
+    fn execute(
+        &mut self,
+        request: &RequestSpec,
+        options: &ClientOptions,
+    ) -> Result<HttpResponse, HttpError>;
+
+    /// Returns the cookies accumulated so far by this client, in the same shape `run` reports
+    /// on the final [`crate::runner::HurlResult`].
+    fn get_cookie_storage(&self) -> Vec<Cookie>;
+}
+
+/// A libcurl-backed HTTP client, able to execute the requests described by a Hurl file.
+pub struct Client {
+    // Read by `execute` once libcurl request dispatch is implemented; unused in this excerpt.
+    #[allow(dead_code)]
+    options: ClientOptions,
+    cookie_storage: Vec<Cookie>,
+}
+
+impl Client {
+    pub fn new(options: &ClientOptions) -> Client {
+        Client {
+            options: options.clone(),
+            cookie_storage: vec![],
+        }
+    }
+
+    pub fn get_cookie_storage(&self) -> Vec<Cookie> {
+        self.cookie_storage.clone()
+    }
+}
+
+impl HttpClient for Client {
+    fn execute(
+        &mut self,
+        _request: &RequestSpec,
+        _options: &ClientOptions,
+    ) -> Result<HttpResponse, HttpError> {
+        unimplemented!("libcurl request execution is not part of this excerpt")
+    }
+
+    fn get_cookie_storage(&self) -> Vec<Cookie> {
+        Client::get_cookie_storage(self)
+    }
+}
+
+/// A trivial in-process [`HttpClient`]: returns pre-recorded responses from a queue instead of
+/// hitting the network. Meant for unit-testing assertions and captures (or exercising
+/// [`crate::runner::run`] itself) without a socket.
+#[derive(Default)]
+pub struct MockHttpClient {
+    responses: std::collections::VecDeque<HttpResponse>,
+    cookie_storage: Vec<Cookie>,
+}
+
+impl MockHttpClient {
+    pub fn new(cookie_storage: Vec<Cookie>) -> MockHttpClient {
+        MockHttpClient {
+            responses: std::collections::VecDeque::new(),
+            cookie_storage,
+        }
+    }
+
+    /// Queues `response` to be returned by the next call to [`HttpClient::execute`].
+    pub fn queue_response(&mut self, response: HttpResponse) {
+        self.responses.push_back(response);
+    }
+}
+
+impl HttpClient for MockHttpClient {
+    fn execute(
+        &mut self,
+        _request: &RequestSpec,
+        _options: &ClientOptions,
+    ) -> Result<HttpResponse, HttpError> {
+        self.responses.pop_front().ok_or_else(|| HttpError {
+            message: "MockHttpClient: no queued response left for this request".to_string(),
+        })
+    }
+
+    fn get_cookie_storage(&self) -> Vec<Cookie> {
+        self.cookie_storage.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_http_client_returns_queued_responses_in_order() {
+        let mut client = MockHttpClient::new(vec![]);
+        client.queue_response(HttpResponse {
+            status: 200,
+            ..HttpResponse::default()
+        });
+        client.queue_response(HttpResponse {
+            status: 404,
+            ..HttpResponse::default()
+        });
+
+        let request = RequestSpec::default();
+        let options = ClientOptions::default();
+        assert_eq!(client.execute(&request, &options).unwrap().status, 200);
+        assert_eq!(client.execute(&request, &options).unwrap().status, 404);
+    }
+
+    #[test]
+    fn mock_http_client_errors_when_queue_is_empty() {
+        let mut client = MockHttpClient::new(vec![]);
+        let request = RequestSpec::default();
+        let options = ClientOptions::default();
+        assert!(client.execute(&request, &options).is_err());
+    }
+
+    #[test]
+    fn mock_http_client_reports_its_configured_cookies() {
+        let cookies = vec![Cookie {
+            name: "session".to_string(),
+            value: "abc".to_string(),
+            domain: "example.com".to_string(),
+        }];
+        let client = MockHttpClient::new(cookies.clone());
+        assert_eq!(client.get_cookie_storage(), cookies);
+    }
+}
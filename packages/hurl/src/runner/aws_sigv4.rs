@@ -0,0 +1,262 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2022 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Signs outgoing requests with AWS Signature Version 4, so Hurl files can drive AWS APIs
+//! (S3, API Gateway, ...) directly, without a signing proxy in front of them.
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// Credentials and scope used to sign a request with SigV4.
+#[derive(Clone, Debug)]
+pub struct AwsSigV4Params {
+    pub region: String,
+    pub service: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub session_token: Option<String>,
+}
+
+/// An HTTP request as seen by the signer: just enough to build the canonical request.
+pub struct RequestToSign<'a> {
+    pub method: &'a str,
+    pub uri: &'a str,
+    pub query: &'a [(String, String)],
+    pub headers: &'a [(String, String)],
+    pub body: &'a [u8],
+}
+
+/// Computes the `Authorization`, `x-amz-date`, `x-amz-content-sha256` and (if a session token is
+/// set) `x-amz-security-token` headers for `request`, signed at `timestamp` (an ISO8601
+/// `yyyyMMddTHHmmssZ` string, e.g. produced from `chrono::Utc::now()`).
+pub fn sign(params: &AwsSigV4Params, request: &RequestToSign, timestamp: &str) -> Vec<(String, String)> {
+    let date = &timestamp[0..8];
+    let payload_hash = sha256_hex(request.body);
+
+    let mut headers = request.headers.to_vec();
+    if let Some(host) = headers
+        .iter_mut()
+        .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+    {
+        host.0 = "host".to_string();
+    } else {
+        headers.push(("host".to_string(), String::new()));
+    }
+    headers.push(("x-amz-date".to_string(), timestamp.to_string()));
+    headers.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+    if let Some(token) = &params.session_token {
+        headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+
+    let canonical_request = canonical_request(params, request, &headers, &payload_hash);
+    let signed_headers = signed_headers(&headers);
+    let scope = format!(
+        "{date}/{region}/{service}/aws4_request",
+        region = params.region,
+        service = params.service
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{hash}",
+        hash = sha256_hex(canonical_request.as_bytes())
+    );
+
+    let signing_key = derive_signing_key(params, date);
+    let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={access_key}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        access_key = params.access_key,
+    );
+
+    let mut result = vec![
+        ("Authorization".to_string(), authorization),
+        ("x-amz-date".to_string(), timestamp.to_string()),
+        ("x-amz-content-sha256".to_string(), payload_hash),
+    ];
+    if let Some(token) = &params.session_token {
+        result.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    result
+}
+
+fn canonical_request(
+    params: &AwsSigV4Params,
+    request: &RequestToSign,
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> String {
+    let canonical_uri = canonical_uri(request.uri, &params.service);
+    let canonical_query = canonical_query_string(request.query);
+    let canonical_headers = canonical_headers(headers);
+    let signed_headers = signed_headers(headers);
+
+    format!(
+        "{method}\n{canonical_uri}\n{query}\n{headers}\n{signed_headers}\n{payload_hash}",
+        method = request.method,
+        query = canonical_query,
+        headers = canonical_headers,
+    )
+}
+
+/// Normalizes and URI-encodes a request path for the canonical request: an empty path
+/// canonicalizes to `/`, and each path segment (but not the separating slashes) is percent
+/// encoded twice, except for the S3 service which only gets a single encoding pass.
+fn canonical_uri(uri: &str, service: &str) -> String {
+    if uri.is_empty() {
+        return "/".to_string();
+    }
+    uri.split('/')
+        .map(|segment| {
+            let encoded = uri_encode(segment);
+            if service == "s3" {
+                encoded
+            } else {
+                uri_encode(&encoded)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query_string(query: &[(String, String)]) -> String {
+    let mut pairs: Vec<(String, String)> = query.to_vec();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", uri_encode(k), uri_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn canonical_headers(headers: &[(String, String)]) -> String {
+    let mut pairs: Vec<(String, String)> = headers
+        .iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{k}:{v}\n"))
+        .collect::<String>()
+}
+
+fn signed_headers(headers: &[(String, String)]) -> String {
+    let mut names: Vec<String> = headers.iter().map(|(k, _)| k.to_ascii_lowercase()).collect();
+    names.sort();
+    names.join(";")
+}
+
+fn derive_signing_key(params: &AwsSigV4Params, date: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", params.secret_key).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, params.region.as_bytes());
+    let k_service = hmac_sha256(&k_region, params.service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex(&hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// URI-encodes a component per the SigV4 spec (RFC 3986 unreserved characters untouched).
+fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Known-answer test derived from AWS's own "vanilla GET" signing example: a bare `GET /`
+    /// against a generic `service` in `us-east-1`, with the example credentials AWS publishes
+    /// in its SigV4 documentation.
+    #[test]
+    fn sign_vanilla_get() {
+        let params = AwsSigV4Params {
+            region: "us-east-1".to_string(),
+            service: "service".to_string(),
+            access_key: "AKIDEXAMPLE".to_string(),
+            secret_key: "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let request = RequestToSign {
+            method: "GET",
+            uri: "/",
+            query: &[],
+            headers: &[("host".to_string(), "example.amazonaws.com".to_string())],
+            body: &[],
+        };
+
+        let signed = sign(&params, &request, "20150830T123600Z");
+        let authorization = signed
+            .iter()
+            .find(|(name, _)| name == "Authorization")
+            .map(|(_, value)| value.clone())
+            .unwrap();
+
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date, \
+             Signature=b0e9826b8e27230263689c913533611258ba50a1cf46f2c0ae5eea5c777359c2"
+        );
+    }
+
+    #[test]
+    fn sign_dedupes_host_header() {
+        let params = AwsSigV4Params {
+            region: "us-east-1".to_string(),
+            service: "service".to_string(),
+            access_key: "AKIDEXAMPLE".to_string(),
+            secret_key: "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let request = RequestToSign {
+            method: "GET",
+            uri: "/",
+            query: &[],
+            headers: &[("Host".to_string(), "example.amazonaws.com".to_string())],
+            body: &[],
+        };
+
+        let signed = sign(&params, &request, "20150830T123600Z");
+        let authorization = signed
+            .iter()
+            .find(|(name, _)| name == "Authorization")
+            .map(|(_, value)| value.clone())
+            .unwrap();
+
+        assert!(authorization.contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date"));
+    }
+}
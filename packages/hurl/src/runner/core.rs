@@ -0,0 +1,98 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2022 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hurl_core::ast::Entry;
+
+use crate::http::{Cookie, ContextDir};
+use crate::runner::aws_sigv4::AwsSigV4Params;
+use crate::runner::error::Error;
+
+/// Options that drive a whole run of a Hurl file, as opposed to [`ClientOptions`] which drive
+/// a single HTTP request/response exchange.
+pub struct RunnerOptions {
+    pub fail_fast: bool,
+    pub variables: HashMap<String, String>,
+    pub to_entry: Option<usize>,
+    pub context_dir: ContextDir,
+    pub ignore_asserts: bool,
+    pub very_verbose: bool,
+    pub pre_entry: Option<fn(Entry) -> bool>,
+    pub post_entry: Option<fn() -> bool>,
+    /// Retry policy applied to an entry whose result has errors.
+    pub retry: Retry,
+    /// Delay to wait between two attempts of the same entry.
+    pub retry_interval: Duration,
+    /// When set, checked at the top of each entry iteration; flipping it to `true` stops the
+    /// run before launching the next entry, letting an embedder cancel a run on shutdown.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// When set, every outgoing request is signed with AWS Signature Version 4 before
+    /// dispatch, so Hurl files can drive AWS APIs directly.
+    pub aws_sigv4: Option<AwsSigV4Params>,
+}
+
+impl Default for RunnerOptions {
+    fn default() -> Self {
+        RunnerOptions {
+            fail_fast: false,
+            variables: HashMap::new(),
+            to_entry: None,
+            context_dir: ContextDir::default(),
+            ignore_asserts: false,
+            very_verbose: false,
+            pre_entry: None,
+            post_entry: None,
+            retry: Retry::None,
+            retry_interval: Duration::from_millis(1000),
+            cancel: None,
+            aws_sigv4: None,
+        }
+    }
+}
+
+/// How many times a failing entry should be re-executed before giving up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Retry {
+    /// Run the entry once, never retry.
+    None,
+    /// Retry up to `n` additional times.
+    Finite(u32),
+    /// Retry forever, until the entry succeeds.
+    Infinite,
+}
+
+/// Result of running a whole Hurl file.
+#[derive(Clone, Debug)]
+pub struct HurlResult {
+    pub filename: String,
+    pub entries: Vec<EntryResult>,
+    pub time_in_ms: u128,
+    pub success: bool,
+    pub cookies: Vec<Cookie>,
+}
+
+/// Result of running a single [`Entry`], possibly after several retries.
+#[derive(Clone, Debug, Default)]
+pub struct EntryResult {
+    pub entry_index: usize,
+    pub time_in_ms: u128,
+    pub errors: Vec<Error>,
+}
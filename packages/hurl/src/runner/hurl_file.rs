@@ -16,11 +16,12 @@
  *
  */
 use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use crate::cli::Logger;
-use crate::http;
-use crate::http::ClientOptions;
+use crate::http::{ClientOptions, HttpClient};
+use crate::runner::core::Retry;
 use crate::runner::entry::get_entry_verbosity;
 use hurl_core::ast::*;
 
@@ -33,6 +34,14 @@ use super::entry;
 /// `filename` and `content` are used to display line base logs (for parsing error or asserts
 /// failures).
 ///
+/// If `runner_options.cancel` is set and flipped to `true` from another thread, the run stops
+/// before starting its next entry and returns a [`HurlResult`] covering the entries completed
+/// so far, instead of panicking or losing partial data.
+///
+/// `http_client` is any [`crate::http::HttpClient`] implementation, not just the libcurl-backed
+/// [`crate::http::Client`]: a mock or recording/replay backend can be driven the same way, with
+/// no change to entry-execution logic.
+///
 /// # Example
 ///
 /// ```
@@ -67,6 +76,10 @@ use super::entry;
 ///        very_verbose: false,
 ///        pre_entry: None,
 ///        post_entry: None,
+///        retry: runner::Retry::None,
+///        retry_interval: std::time::Duration::from_millis(1000),
+///        cancel: None,
+///        aws_sigv4: None,
 ///  };
 ///
 /// // Run the hurl file
@@ -83,7 +96,7 @@ use super::entry;
 pub fn run(
     hurl_file: &HurlFile,
     filename: &str,
-    http_client: &mut http::Client,
+    http_client: &mut dyn HttpClient,
     runner_options: &RunnerOptions,
     client_options: &ClientOptions,
     logger: &Logger,
@@ -109,6 +122,13 @@ pub fn run(
         .enumerate()
         .collect::<Vec<(usize, &Entry)>>()
     {
+        if let Some(cancel) = &runner_options.cancel {
+            if cancel.load(Ordering::SeqCst) {
+                logger.debug_important("Run cancelled, stopping before next entry");
+                break;
+            }
+        }
+
         if let Some(pre_entry) = runner_options.pre_entry {
             let exit = pre_entry(entry.clone());
             if exit {
@@ -134,14 +154,36 @@ pub fn run(
 
         let client_options = entry::get_entry_options(entry, client_options, logger);
 
-        let entry_results = entry::run(
-            entry,
-            http_client,
-            &mut variables,
-            runner_options,
-            &client_options,
-            logger,
-        );
+        // Snapshot variables before the first attempt so that a retry always starts from the
+        // same state: captures from a failed attempt must not leak into the next one.
+        let variables_snapshot = variables.clone();
+        let mut attempt = 0;
+        let entry_results = loop {
+            attempt += 1;
+            variables = variables_snapshot.clone();
+
+            let entry_results = entry::run(
+                entry,
+                http_client,
+                &mut variables,
+                runner_options,
+                &client_options,
+                logger,
+            );
+
+            let cancelled = runner_options
+                .cancel
+                .as_ref()
+                .is_some_and(|cancel| cancel.load(Ordering::SeqCst));
+
+            if should_retry(&entry_results, attempt, runner_options.retry, cancelled) {
+                logger.debug(format!("retrying entry {} (attempt {attempt})", entry_index + 1).as_str());
+                std::thread::sleep(runner_options.retry_interval);
+                continue;
+            }
+
+            break entry_results;
+        };
 
         for entry_result in &entry_results {
             for e in &entry_result.errors {
@@ -157,7 +199,10 @@ pub fn run(
             }
         }
 
-        if runner_options.fail_fast && !entry_results.last().unwrap().errors.is_empty() {
+        let last_entry_failed = entry_results
+            .last()
+            .is_none_or(|result| !result.errors.is_empty());
+        if runner_options.fail_fast && last_entry_failed {
             break;
         }
     }
@@ -179,3 +224,123 @@ pub fn run(
         cookies,
     }
 }
+
+/// Decides whether the just-completed `attempt` (1-based, including this one) of an entry should
+/// be retried, given the `entry_results` it produced and the run's retry policy.
+///
+/// An empty `entry_results` means `entry::run` couldn't even produce a transport error (e.g. it
+/// bailed out before issuing a request); that's treated as a failure, but never retried, since
+/// there is no `EntryResult` to tell us whether retrying makes sense. A cancelled run is never
+/// retried either, so a pending cancellation always wins over a pending retry.
+fn should_retry(entry_results: &[EntryResult], attempt: u32, retry: Retry, cancelled: bool) -> bool {
+    if cancelled {
+        return false;
+    }
+    let Some(last) = entry_results.last() else {
+        return false;
+    };
+    if last.errors.is_empty() {
+        return false;
+    }
+    match retry {
+        Retry::None => false,
+        Retry::Finite(max) => attempt <= max,
+        Retry::Infinite => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use crate::http::{ClientOptions, Cookie, MockHttpClient};
+    use crate::runner::error::Error;
+
+    use super::*;
+
+    fn error() -> Error {
+        Error {
+            message: "boom".to_string(),
+        }
+    }
+
+    fn result_with_errors(errors: Vec<Error>) -> Vec<EntryResult> {
+        vec![EntryResult {
+            entry_index: 0,
+            time_in_ms: 0,
+            errors,
+        }]
+    }
+
+    #[test]
+    fn should_retry_none_policy_never_retries() {
+        let results = result_with_errors(vec![error()]);
+        assert!(!should_retry(&results, 1, Retry::None, false));
+    }
+
+    #[test]
+    fn should_retry_finite_policy_retries_until_exhausted() {
+        let results = result_with_errors(vec![error()]);
+        assert!(should_retry(&results, 1, Retry::Finite(2), false));
+        assert!(should_retry(&results, 2, Retry::Finite(2), false));
+        assert!(!should_retry(&results, 3, Retry::Finite(2), false));
+    }
+
+    #[test]
+    fn should_retry_infinite_policy_always_retries_while_failing() {
+        let results = result_with_errors(vec![error()]);
+        assert!(should_retry(&results, 1, Retry::Infinite, false));
+        assert!(should_retry(&results, 1000, Retry::Infinite, false));
+    }
+
+    #[test]
+    fn should_retry_never_retries_a_successful_attempt() {
+        let results = result_with_errors(vec![]);
+        assert!(!should_retry(&results, 1, Retry::Infinite, false));
+    }
+
+    #[test]
+    fn should_retry_never_retries_once_cancelled() {
+        let results = result_with_errors(vec![error()]);
+        assert!(!should_retry(&results, 1, Retry::Infinite, true));
+    }
+
+    #[test]
+    fn should_retry_never_retries_an_empty_result_list() {
+        assert!(!should_retry(&[], 1, Retry::Infinite, false));
+    }
+
+    #[test]
+    fn run_stops_before_first_entry_when_already_cancelled() {
+        let s = "GET http://localhost:8000/hello\nHTTP/1.0 200\n";
+        let hurl_file = hurl_core::parser::parse_hurl_file(s).unwrap();
+        let logger = Logger::new(false, false, "sample.hurl", s);
+
+        let mut client = MockHttpClient::new(vec![Cookie {
+            name: "session".to_string(),
+            value: "abc".to_string(),
+            domain: "example.com".to_string(),
+        }]);
+
+        let runner_options = RunnerOptions {
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+            ..RunnerOptions::default()
+        };
+
+        let hurl_results = run(
+            &hurl_file,
+            "sample.hurl",
+            &mut client,
+            &runner_options,
+            &ClientOptions::default(),
+            &logger,
+        );
+
+        // Cancellation is checked before the first entry is run, so `entry::run` (which is not
+        // implemented in this excerpt) is never reached.
+        assert!(hurl_results.entries.is_empty());
+        assert!(hurl_results.success);
+        assert_eq!(hurl_results.cookies.len(), 1);
+    }
+}
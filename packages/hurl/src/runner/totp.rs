@@ -0,0 +1,257 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2022 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Implements the `totp(secret)` templating function (RFC 6238), so a Hurl file can authenticate
+//! against endpoints protected by a time-based one-time password without a helper script.
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+use crate::runner::error::Error;
+
+/// HMAC algorithm used to derive the TOTP, as allowed by RFC 6238.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TotpAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+/// Parameters of the `totp(secret, ...)` function, all but `secret` optional.
+#[derive(Clone, Debug)]
+pub struct TotpParams {
+    pub secret: String,
+    pub digits: u32,
+    pub period: u64,
+    pub algorithm: TotpAlgorithm,
+}
+
+impl TotpParams {
+    pub fn new(secret: &str) -> TotpParams {
+        TotpParams {
+            secret: secret.to_string(),
+            digits: 6,
+            period: 30,
+            algorithm: TotpAlgorithm::Sha1,
+        }
+    }
+}
+
+/// Returns the current TOTP code for `params`, evaluated against the wall clock, zero-padded to
+/// `params.digits` digits.
+///
+/// Must be called again on every attempt (e.g. a retried entry), since the code depends on the
+/// current time counter.
+pub fn eval(params: &TotpParams, unix_time: u64) -> Result<String, Error> {
+    let key = base32_decode(&params.secret).ok_or_else(|| Error {
+        message: format!("Invalid base32 TOTP secret: {}", params.secret),
+    })?;
+    let counter = unix_time / params.period;
+    let msg = counter.to_be_bytes();
+
+    let hash = match params.algorithm {
+        TotpAlgorithm::Sha1 => hmac_sha1(&key, &msg),
+        TotpAlgorithm::Sha256 => hmac_sha256(&key, &msg),
+        TotpAlgorithm::Sha512 => hmac_sha512(&key, &msg),
+    };
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = ((hash[offset] as u32 & 0x7f) << 24)
+        | ((hash[offset + 1] as u32) << 16)
+        | ((hash[offset + 2] as u32) << 8)
+        | (hash[offset + 3] as u32);
+
+    if params.digits == 0 || params.digits > 9 {
+        return Err(Error {
+            message: format!(
+                "Invalid totp digits: {} (must be between 1 and 9)",
+                params.digits
+            ),
+        });
+    }
+    let modulus = 10u32.pow(params.digits);
+    Ok(format!(
+        "{:0width$}",
+        binary % modulus,
+        width = params.digits as usize
+    ))
+}
+
+/// Renders every `{{ totp(...) }}` call found in `text`, replacing it with a fresh TOTP code
+/// computed against `unix_time`. Any other `{{ ... }}` template expression is left untouched, so
+/// this can run as one step of the broader template rendering pipeline. This is the entry point
+/// used by [`crate::runner::entry::run`] to resolve `totp` ahead of dispatching a request.
+pub fn render(text: &str, unix_time: u64) -> Result<String, Error> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            result.push_str(rest);
+            rest = "";
+            break;
+        };
+        let end = start + end;
+        let expr = rest[start + 2..end].trim();
+
+        result.push_str(&rest[..start]);
+        if let Some(params) = parse_call(expr) {
+            result.push_str(&eval(&params, unix_time)?);
+        } else {
+            result.push_str(&rest[start..end + 2]);
+        }
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Parses a `totp(secret)` or `totp(secret, digits=8, period=30, algorithm=sha256)` call.
+/// Returns `None` if `expr` is not a `totp` call, so callers can fall back to other template
+/// functions/variables.
+fn parse_call(expr: &str) -> Option<TotpParams> {
+    let inner = expr.strip_prefix("totp(")?.strip_suffix(')')?;
+    let mut args = inner.split(',').map(str::trim);
+
+    let secret = args.next()?.trim_matches(|c| c == '"' || c == '\'');
+    let mut params = TotpParams::new(secret);
+
+    for arg in args {
+        let (name, value) = arg.split_once('=')?;
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        match name.trim() {
+            "digits" => params.digits = value.parse().ok()?,
+            "period" => params.period = value.parse().ok()?,
+            "algorithm" => {
+                params.algorithm = match value.to_ascii_lowercase().as_str() {
+                    "sha1" => TotpAlgorithm::Sha1,
+                    "sha256" => TotpAlgorithm::Sha256,
+                    "sha512" => TotpAlgorithm::Sha512,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(params)
+}
+
+fn hmac_sha1(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hmac_sha512(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Decodes an RFC 4648 base32 string (the conventional encoding of a TOTP shared secret),
+/// ignoring `=` padding. Returns `None` on an invalid alphabet character.
+fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    let mut bits = 0u64;
+    let mut bit_count = 0u32;
+    let mut out = vec![];
+
+    for c in input.trim_end_matches('=').chars() {
+        let value = ALPHABET
+            .iter()
+            .position(|&b| b == c.to_ascii_uppercase() as u8)?;
+        bits = (bits << 5) | value as u64;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 Appendix B known-answer vectors, at T=59s (time counter 1), 8 digits, period 30s.
+    // Seeds are the ASCII strings "12345678901234567890" (SHA1), repeated to 32/64 bytes for
+    // SHA256/SHA512, base32-encoded since `eval` takes a base32 secret like a real TOTP app would.
+
+    #[test]
+    fn eval_rfc6238_sha1_vector() {
+        let params = TotpParams {
+            secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ".to_string(),
+            digits: 8,
+            period: 30,
+            algorithm: TotpAlgorithm::Sha1,
+        };
+        assert_eq!(eval(&params, 59).unwrap(), "94287082");
+    }
+
+    #[test]
+    fn eval_rfc6238_sha256_vector() {
+        let params = TotpParams {
+            secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA====".to_string(),
+            digits: 8,
+            period: 30,
+            algorithm: TotpAlgorithm::Sha256,
+        };
+        assert_eq!(eval(&params, 59).unwrap(), "46119246");
+    }
+
+    #[test]
+    fn eval_rfc6238_sha512_vector() {
+        let params = TotpParams {
+            secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA="
+                .to_string(),
+            digits: 8,
+            period: 30,
+            algorithm: TotpAlgorithm::Sha512,
+        };
+        assert_eq!(eval(&params, 59).unwrap(), "90693936");
+    }
+
+    #[test]
+    fn eval_rejects_digits_out_of_range() {
+        let params = TotpParams {
+            digits: 10,
+            ..TotpParams::new("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ")
+        };
+        assert!(eval(&params, 59).is_err());
+    }
+
+    #[test]
+    fn render_substitutes_totp_call() {
+        let text = "code={{ totp(\"GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\", digits=8) }}&other={{ var }}";
+        let rendered = render(text, 59).unwrap();
+        assert_eq!(rendered, "code=94287082&other={{ var }}");
+    }
+
+    #[test]
+    fn render_passes_through_non_totp_expressions() {
+        assert_eq!(render("hello {{ name }}", 0).unwrap(), "hello {{ name }}");
+    }
+}